@@ -1,11 +1,69 @@
-#[cfg(target_os = "windows")]
 use std::{
   fs,
-  path::PathBuf,
+  path::{Path, PathBuf},
   process::Command,
+  sync::Mutex,
   time::{SystemTime, UNIX_EPOCH},
 };
 
+/// PID del proceso de sintesis en curso, para poder cancelarlo desde la UI.
+static AUDIO_EXPORT_CHILD: Mutex<Option<u32>> = Mutex::new(None);
+
+/// Reproductor de la vista previa en curso; `stop_preview` lo detiene y el hilo
+/// de reproduccion limpia el WAV temporal al terminar.
+static PREVIEW_SINK: Mutex<Option<std::sync::Arc<rodio::Sink>>> = Mutex::new(None);
+
+fn register_audio_export_child(pid: u32) {
+  if let Ok(mut guard) = AUDIO_EXPORT_CHILD.lock() {
+    *guard = Some(pid);
+  }
+}
+
+fn clear_audio_export_child() {
+  if let Ok(mut guard) = AUDIO_EXPORT_CHILD.lock() {
+    *guard = None;
+  }
+}
+
+#[cfg(target_os = "windows")]
+fn kill_audio_export_process(pid: u32) -> Result<(), String> {
+  Command::new("taskkill")
+    .args(["/PID", &pid.to_string(), "/T", "/F"])
+    .output()
+    .map_err(|error| format!("No se pudo cancelar la exportacion de audio: {error}"))?;
+  Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn kill_audio_export_process(pid: u32) -> Result<(), String> {
+  Command::new("kill")
+    .arg(pid.to_string())
+    .output()
+    .map_err(|error| format!("No se pudo cancelar la exportacion de audio: {error}"))?;
+  Ok(())
+}
+
+/// Progreso de sintesis emitido hacia el frontend por el evento
+/// `audiobook-export-progress` (caracteres hablados sobre el total).
+#[cfg(target_os = "windows")]
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AudiobookProgress {
+  spoken: usize,
+  total: usize,
+}
+
+/// Solicita la cancelacion de la exportacion de audio en curso terminando el
+/// proceso hijo de sintesis, al estilo de un bucle que reacciona a una senal.
+#[tauri::command]
+fn cancel_audiobook_export() -> Result<(), String> {
+  let pid = AUDIO_EXPORT_CHILD.lock().ok().and_then(|mut guard| guard.take());
+  match pid {
+    Some(pid) => kill_audio_export_process(pid),
+    None => Ok(()),
+  }
+}
+
 #[derive(serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ExportAudiobookInput {
@@ -15,6 +73,11 @@ struct ExportAudiobookInput {
   voice_name: Option<String>,
   rate: f32,
   volume: f32,
+  /// Plantilla de comando del codificador externo (ffmpeg/lame/oggenc); admite
+  /// los marcadores `{input}` y `{output}`. Vacio = se deja el WAV sin codificar.
+  encoder_command: Option<String>,
+  /// Formato de destino informado por la UI (mp3, m4b, ogg, ...).
+  target_format: Option<String>,
 }
 
 #[cfg(target_os = "windows")]
@@ -80,6 +143,15 @@ try {
 
   $synth.Rate = $rate
   $synth.Volume = $volume
+
+  # Emite marcadores de progreso (caracteres hablados / total) que la capa Rust
+  # parsea linea a linea y reenvia al frontend como eventos de Tauri.
+  $total = $text.Length
+  $synth.add_SpeakProgress({
+    param($sender, $eventArgs)
+    [Console]::Out.WriteLine("PROGRESS " + $eventArgs.CharacterPosition + " " + $total)
+  })
+
   $synth.SetOutputToWaveFile($outPath)
   try {
     $synth.Speak($text)
@@ -87,13 +159,13 @@ try {
     $synth.SetOutputToNull()
   }
 
+  Write-Output ("PROGRESS " + $total + " " + $total)
   Write-Output $outPath
 } finally {
   $synth.Dispose()
 }
 "#;
 
-#[cfg(target_os = "windows")]
 fn build_temp_audio_text_path() -> PathBuf {
   let mut path = std::env::temp_dir();
   let stamp = SystemTime::now()
@@ -104,6 +176,74 @@ fn build_temp_audio_text_path() -> PathBuf {
   path
 }
 
+fn build_temp_audio_wav_path() -> PathBuf {
+  let mut path = std::env::temp_dir();
+  let stamp = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|duration| duration.as_millis())
+    .unwrap_or(0);
+  path.push(format!("writewme-audio-{}-{stamp}.wav", std::process::id()));
+  path
+}
+
+/// Ajusta la extension de `output_path` al formato pedido por la UI cuando
+/// difiere, para que el archivo codificado termine con la extension correcta.
+fn apply_target_format(output_path: &str, target_format: &str) -> String {
+  let format = target_format.trim().trim_start_matches('.').to_lowercase();
+  if format.is_empty() {
+    return output_path.to_string();
+  }
+  let path = Path::new(output_path);
+  let already_matches = path
+    .extension()
+    .and_then(|ext| ext.to_str())
+    .is_some_and(|ext| ext.eq_ignore_ascii_case(&format));
+  if already_matches {
+    output_path.to_string()
+  } else {
+    path.with_extension(&format).to_string_lossy().to_string()
+  }
+}
+
+/// Ejecuta el codificador externo sustituyendo `{input}`/`{output}` en la
+/// plantilla y devuelve la ruta final codificada; en error propaga su stderr
+/// igual que la rama de sintesis. `target_format` ajusta la extension de salida.
+fn run_audio_encoder(
+  template: &str,
+  input_wav: &Path,
+  output_path: &str,
+  target_format: &str,
+) -> Result<String, String> {
+  let output_path = apply_target_format(output_path, target_format);
+  let output_path = output_path.as_str();
+  let input_wav = input_wav.to_string_lossy();
+  let tokens: Vec<String> = template
+    .split_whitespace()
+    .map(|token| token.replace("{input}", &input_wav).replace("{output}", output_path))
+    .collect();
+
+  let (program, args) = tokens
+    .split_first()
+    .ok_or_else(|| "No se configuro un codificador de audio.".to_string())?;
+
+  let output = Command::new(program)
+    .args(args)
+    .output()
+    .map_err(|error| format!("No se pudo iniciar el codificador de audio: {error}"))?;
+
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let detail = if !stderr.is_empty() {
+      stderr
+    } else {
+      format!("El codificador finalizo con codigo {:?}", output.status.code())
+    };
+    return Err(format!("Fallo al codificar el audio: {detail}"));
+  }
+
+  Ok(output_path.to_string())
+}
+
 #[cfg(target_os = "windows")]
 fn map_audio_rate_to_sapi(rate: f32) -> i32 {
   let clamped = rate.clamp(0.5, 2.0);
@@ -121,7 +261,10 @@ fn map_audio_volume_to_sapi(volume: f32) -> i32 {
 
 #[cfg(target_os = "windows")]
 #[tauri::command]
-fn export_audiobook_wav(input: ExportAudiobookInput) -> Result<String, String> {
+fn export_audiobook_wav(app: tauri::AppHandle, input: ExportAudiobookInput) -> Result<String, String> {
+  use std::io::BufRead;
+  use tauri::Emitter;
+
   let normalized_text = input.text.trim();
   if normalized_text.is_empty() {
     return Err("No hay texto para exportar.".into());
@@ -132,19 +275,369 @@ fn export_audiobook_wav(input: ExportAudiobookInput) -> Result<String, String> {
     return Err("No se encontro la ruta de salida para el audiolibro.".into());
   }
 
+  // Cuando se pide codificacion posterior, se sintetiza a un WAV temporal y ese
+  // archivo alimenta al codificador externo; si no, se escribe en output_path.
+  let encoder_command = input.encoder_command.clone().unwrap_or_default();
+  let encoder_command = encoder_command.trim().to_string();
+  let wav_target_buf = if encoder_command.is_empty() {
+    PathBuf::from(output_path)
+  } else {
+    build_temp_audio_wav_path()
+  };
+  let wav_target = wav_target_buf.to_string_lossy().to_string();
+
   let text_path = build_temp_audio_text_path();
   fs::write(&text_path, normalized_text.as_bytes())
     .map_err(|error| format!("No se pudo preparar el texto para audio: {error}"))?;
 
   let requested_voice = input.voice_name.unwrap_or_default().trim().to_string();
-  let command_result = Command::new("powershell")
+  let spawn_result = Command::new("powershell")
     .args(["-NoProfile", "-NonInteractive", "-Command", AUDIO_EXPORT_SCRIPT])
     .env("WRITEWME_AUDIO_TEXT_PATH", &text_path)
-    .env("WRITEWME_AUDIO_OUTPUT", output_path)
+    .env("WRITEWME_AUDIO_OUTPUT", &wav_target)
     .env("WRITEWME_AUDIO_LANGUAGE", input.language.trim())
     .env("WRITEWME_AUDIO_VOICE", requested_voice)
     .env("WRITEWME_AUDIO_RATE_SAPI", map_audio_rate_to_sapi(input.rate).to_string())
     .env("WRITEWME_AUDIO_VOLUME_SAPI", map_audio_volume_to_sapi(input.volume).to_string())
+    .stdout(std::process::Stdio::piped())
+    .stderr(std::process::Stdio::piped())
+    .spawn();
+
+  let mut child = match spawn_result {
+    Ok(child) => child,
+    Err(error) => {
+      let _ = fs::remove_file(&text_path);
+      return Err(format!("No se pudo iniciar la exportacion de audio: {error}"));
+    }
+  };
+
+  register_audio_export_child(child.id());
+
+  // stderr se drena en un hilo aparte: si se leyera despues de stdout, un libro
+  // que emitiera mas de lo que cabe en el buffer del pipe (~64 KB) bloquearia al
+  // hijo y colgaria la exportacion.
+  let stderr_reader = child.stderr.take().map(|stderr| {
+    std::thread::spawn(move || {
+      use std::io::Read;
+      let mut buffer = Vec::new();
+      let _ = std::io::BufReader::new(stderr).read_to_end(&mut buffer);
+      buffer
+    })
+  });
+
+  // El script escribe marcadores `PROGRESS hablados total` y, al final, la ruta
+  // de salida; se reemiten los primeros como eventos y se guarda la ultima linea.
+  let mut exported_path = String::new();
+  if let Some(stdout) = child.stdout.take() {
+    let reader = std::io::BufReader::new(stdout);
+    for line in reader.lines() {
+      let line = match line {
+        Ok(line) => line,
+        Err(_) => break,
+      };
+      let trimmed = line.trim();
+      if trimmed.is_empty() {
+        continue;
+      }
+      if let Some(rest) = trimmed.strip_prefix("PROGRESS ") {
+        let mut parts = rest.split_whitespace();
+        if let (Some(spoken), Some(total)) = (parts.next(), parts.next()) {
+          if let (Ok(spoken), Ok(total)) = (spoken.parse::<usize>(), total.parse::<usize>()) {
+            let _ = app.emit("audiobook-export-progress", AudiobookProgress { spoken, total });
+          }
+        }
+      } else {
+        exported_path = trimmed.to_string();
+      }
+    }
+  }
+
+  let command_result = child.wait();
+  clear_audio_export_child();
+  let _ = fs::remove_file(&text_path);
+
+  let stderr_bytes = stderr_reader
+    .and_then(|handle| handle.join().ok())
+    .unwrap_or_default();
+
+  let status = command_result.map_err(|error| format!("No se pudo iniciar la exportacion de audio: {error}"))?;
+  if !status.success() {
+    let stderr = String::from_utf8_lossy(&stderr_bytes).trim().to_string();
+    let detail = if !stderr.is_empty() {
+      stderr
+    } else if !exported_path.is_empty() {
+      exported_path.clone()
+    } else {
+      format!("PowerShell finalizo con codigo {:?}", status.code())
+    };
+    return Err(format!("Fallo al generar WAV: {detail}"));
+  }
+
+  if !encoder_command.is_empty() {
+    let encoded = run_audio_encoder(
+      &encoder_command,
+      &wav_target_buf,
+      output_path,
+      input.target_format.as_deref().unwrap_or_default(),
+    );
+    let _ = fs::remove_file(&wav_target_buf);
+    return encoded;
+  }
+
+  if exported_path.is_empty() {
+    Ok(output_path.to_string())
+  } else {
+    Ok(exported_path)
+  }
+}
+
+#[cfg(target_os = "macos")]
+fn map_audio_rate_to_say(rate: f32) -> i32 {
+  // `say` mide en palabras por minuto; ~175 es la cadencia natural.
+  ((rate.clamp(0.5, 2.0) * 175.0).round() as i32).clamp(90, 350)
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn export_audiobook_wav(input: ExportAudiobookInput) -> Result<String, String> {
+  let normalized_text = input.text.trim();
+  if normalized_text.is_empty() {
+    return Err("No hay texto para exportar.".into());
+  }
+
+  let output_path = input.output_path.trim();
+  if output_path.is_empty() {
+    return Err("No se encontro la ruta de salida para el audiolibro.".into());
+  }
+
+  if let Some(parent) = PathBuf::from(output_path).parent() {
+    if !parent.as_os_str().is_empty() {
+      fs::create_dir_all(parent)
+        .map_err(|error| format!("No se pudo preparar la carpeta de salida: {error}"))?;
+    }
+  }
+
+  let encoder_command = input.encoder_command.clone().unwrap_or_default();
+  let encoder_command = encoder_command.trim().to_string();
+  let wav_target_buf = if encoder_command.is_empty() {
+    PathBuf::from(output_path)
+  } else {
+    build_temp_audio_wav_path()
+  };
+  let wav_target = wav_target_buf.to_string_lossy().to_string();
+
+  // `say` lee la seleccion desde un archivo con -f para evitar limites de argumentos;
+  // el volumen se ajusta con el comando embebido [[volm ...]] al inicio del texto.
+  let volume = input.volume.clamp(0.0, 1.0);
+  let text_with_volume = format!("[[volm {volume:.2}]]{normalized_text}");
+  let text_path = build_temp_audio_text_path();
+  fs::write(&text_path, text_with_volume.as_bytes())
+    .map_err(|error| format!("No se pudo preparar el texto para audio: {error}"))?;
+
+  let mut command = Command::new("say");
+  command
+    .arg("-o")
+    .arg(&wav_target)
+    .args(["--file-format=WAVE", "--data-format=LEI16@22050"])
+    .arg("-r")
+    .arg(map_audio_rate_to_say(input.rate).to_string());
+
+  let requested_voice = input.voice_name.unwrap_or_default().trim().to_string();
+  if !requested_voice.is_empty() {
+    command.arg("-v").arg(&requested_voice);
+  }
+
+  command.arg("-f").arg(&text_path);
+
+  let spawn_result = command
+    .stdout(std::process::Stdio::piped())
+    .stderr(std::process::Stdio::piped())
+    .spawn();
+  let child = match spawn_result {
+    Ok(child) => child,
+    Err(error) => {
+      let _ = fs::remove_file(&text_path);
+      return Err(format!("No se pudo iniciar la exportacion de audio: {error}"));
+    }
+  };
+  register_audio_export_child(child.id());
+
+  let command_result = child.wait_with_output();
+  clear_audio_export_child();
+  let _ = fs::remove_file(&text_path);
+
+  let output = command_result.map_err(|error| format!("No se pudo iniciar la exportacion de audio: {error}"))?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let detail = if !stderr.is_empty() {
+      stderr
+    } else {
+      format!("say finalizo con codigo {:?}", output.status.code())
+    };
+    return Err(format!("Fallo al generar WAV: {detail}"));
+  }
+
+  if !encoder_command.is_empty() {
+    let encoded = run_audio_encoder(
+      &encoder_command,
+      &wav_target_buf,
+      output_path,
+      input.target_format.as_deref().unwrap_or_default(),
+    );
+    let _ = fs::remove_file(&wav_target_buf);
+    return encoded;
+  }
+
+  Ok(output_path.to_string())
+}
+
+// El backend de Linux sintetiza con espeak-ng en lugar de speech-dispatcher:
+// `spd-say` no sabe capturar a fichero (solo reproduce por el demonio), asi que
+// no puede cumplir el contrato de escribir un WAV. Tanto la exportacion como la
+// enumeracion de voces (`list_audiobook_voices`) usan espeak-ng para que el
+// selector y el sintetizador hablen de las mismas voces.
+#[cfg(target_os = "linux")]
+fn map_audio_rate_to_espeak(rate: f32) -> i32 {
+  // espeak-ng mide la cadencia en palabras por minuto; ~175 es la natural.
+  ((rate.clamp(0.5, 2.0) * 175.0).round() as i32).clamp(80, 450)
+}
+
+#[cfg(target_os = "linux")]
+fn map_audio_volume_to_espeak(volume: f32) -> i32 {
+  // `-a` acepta 0..200, con 100 como volumen normal.
+  (volume.clamp(0.0, 1.0) * 200.0).round() as i32
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+fn export_audiobook_wav(input: ExportAudiobookInput) -> Result<String, String> {
+  let normalized_text = input.text.trim();
+  if normalized_text.is_empty() {
+    return Err("No hay texto para exportar.".into());
+  }
+
+  let output_path = input.output_path.trim();
+  if output_path.is_empty() {
+    return Err("No se encontro la ruta de salida para el audiolibro.".into());
+  }
+
+  if let Some(parent) = PathBuf::from(output_path).parent() {
+    if !parent.as_os_str().is_empty() {
+      fs::create_dir_all(parent)
+        .map_err(|error| format!("No se pudo preparar la carpeta de salida: {error}"))?;
+    }
+  }
+
+  let encoder_command = input.encoder_command.clone().unwrap_or_default();
+  let encoder_command = encoder_command.trim().to_string();
+  let wav_target_buf = if encoder_command.is_empty() {
+    PathBuf::from(output_path)
+  } else {
+    build_temp_audio_wav_path()
+  };
+  let wav_target = wav_target_buf.to_string_lossy().to_string();
+
+  let text_path = build_temp_audio_text_path();
+  fs::write(&text_path, normalized_text.as_bytes())
+    .map_err(|error| format!("No se pudo preparar el texto para audio: {error}"))?;
+
+  // espeak-ng renderiza directamente a WAV con `-w <archivo>` (spd-say no puede
+  // capturar a fichero); `-s` fija la cadencia, `-a` el volumen y `-v` la voz.
+  let mut command = Command::new("espeak-ng");
+  command
+    .arg("-w")
+    .arg(&wav_target)
+    .arg("-s")
+    .arg(map_audio_rate_to_espeak(input.rate).to_string())
+    .arg("-a")
+    .arg(map_audio_volume_to_espeak(input.volume).to_string());
+
+  // espeak-ng selecciona voz e idioma con el mismo flag `-v`; la voz explicita
+  // tiene prioridad sobre el idioma del documento.
+  let requested_voice = input.voice_name.unwrap_or_default().trim().to_string();
+  let voice = if requested_voice.is_empty() {
+    input.language.trim().to_string()
+  } else {
+    requested_voice
+  };
+  if !voice.is_empty() {
+    command.arg("-v").arg(&voice);
+  }
+
+  command.arg("-f").arg(&text_path);
+
+  let spawn_result = command
+    .stdout(std::process::Stdio::piped())
+    .stderr(std::process::Stdio::piped())
+    .spawn();
+  let child = match spawn_result {
+    Ok(child) => child,
+    Err(error) => {
+      let _ = fs::remove_file(&text_path);
+      return Err(format!("No se pudo iniciar la exportacion de audio: {error}"));
+    }
+  };
+  register_audio_export_child(child.id());
+
+  let command_result = child.wait_with_output();
+  clear_audio_export_child();
+  let _ = fs::remove_file(&text_path);
+
+  let output = command_result.map_err(|error| format!("No se pudo iniciar la exportacion de audio: {error}"))?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let detail = if !stderr.is_empty() {
+      stderr
+    } else {
+      format!("espeak-ng finalizo con codigo {:?}", output.status.code())
+    };
+    return Err(format!("Fallo al generar WAV: {detail}"));
+  }
+
+  if !encoder_command.is_empty() {
+    let encoded = run_audio_encoder(
+      &encoder_command,
+      &wav_target_buf,
+      output_path,
+      input.target_format.as_deref().unwrap_or_default(),
+    );
+    let _ = fs::remove_file(&wav_target_buf);
+    return encoded;
+  }
+
+  Ok(output_path.to_string())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+#[tauri::command]
+fn export_audiobook_wav(_input: ExportAudiobookInput) -> Result<String, String> {
+  Err("La exportacion de audiolibro WAV con voces del sistema no esta disponible en esta plataforma.".into())
+}
+
+/// Sintetiza un unico segmento a un WAV concreto reutilizando el backend del
+/// sistema, sin progreso ni codificacion posterior. Lo usa la exportacion por
+/// capitulos, que escribe un archivo por segmento.
+#[cfg(target_os = "windows")]
+fn synthesize_segment_to_wav(
+  text: &str,
+  wav_path: &str,
+  language: &str,
+  voice: &str,
+  rate: f32,
+  volume: f32,
+) -> Result<(), String> {
+  let text_path = build_temp_audio_text_path();
+  fs::write(&text_path, text.as_bytes())
+    .map_err(|error| format!("No se pudo preparar el texto para audio: {error}"))?;
+
+  let command_result = Command::new("powershell")
+    .args(["-NoProfile", "-NonInteractive", "-Command", AUDIO_EXPORT_SCRIPT])
+    .env("WRITEWME_AUDIO_TEXT_PATH", &text_path)
+    .env("WRITEWME_AUDIO_OUTPUT", wav_path)
+    .env("WRITEWME_AUDIO_LANGUAGE", language)
+    .env("WRITEWME_AUDIO_VOICE", voice)
+    .env("WRITEWME_AUDIO_RATE_SAPI", map_audio_rate_to_sapi(rate).to_string())
+    .env("WRITEWME_AUDIO_VOLUME_SAPI", map_audio_volume_to_sapi(volume).to_string())
     .output();
 
   let _ = fs::remove_file(&text_path);
@@ -152,35 +645,703 @@ fn export_audiobook_wav(input: ExportAudiobookInput) -> Result<String, String> {
   let output = command_result.map_err(|error| format!("No se pudo iniciar la exportacion de audio: {error}"))?;
   if !output.status.success() {
     let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
     let detail = if !stderr.is_empty() {
       stderr
-    } else if !stdout.is_empty() {
-      stdout
     } else {
       format!("PowerShell finalizo con codigo {:?}", output.status.code())
     };
     return Err(format!("Fallo al generar WAV: {detail}"));
   }
 
-  let exported_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-  if exported_path.is_empty() {
-    Ok(output_path.to_string())
+  Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn synthesize_segment_to_wav(
+  text: &str,
+  wav_path: &str,
+  _language: &str,
+  voice: &str,
+  rate: f32,
+  volume: f32,
+) -> Result<(), String> {
+  let volume = volume.clamp(0.0, 1.0);
+  let text_with_volume = format!("[[volm {volume:.2}]]{text}");
+  let text_path = build_temp_audio_text_path();
+  fs::write(&text_path, text_with_volume.as_bytes())
+    .map_err(|error| format!("No se pudo preparar el texto para audio: {error}"))?;
+
+  let mut command = Command::new("say");
+  command
+    .arg("-o")
+    .arg(wav_path)
+    .args(["--file-format=WAVE", "--data-format=LEI16@22050"])
+    .arg("-r")
+    .arg(map_audio_rate_to_say(rate).to_string());
+  if !voice.is_empty() {
+    command.arg("-v").arg(voice);
+  }
+  command.arg("-f").arg(&text_path);
+
+  let command_result = command.output();
+  let _ = fs::remove_file(&text_path);
+
+  let output = command_result.map_err(|error| format!("No se pudo iniciar la exportacion de audio: {error}"))?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let detail = if !stderr.is_empty() {
+      stderr
+    } else {
+      format!("say finalizo con codigo {:?}", output.status.code())
+    };
+    return Err(format!("Fallo al generar WAV: {detail}"));
+  }
+
+  Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn synthesize_segment_to_wav(
+  text: &str,
+  wav_path: &str,
+  language: &str,
+  voice: &str,
+  rate: f32,
+  volume: f32,
+) -> Result<(), String> {
+  let text_path = build_temp_audio_text_path();
+  fs::write(&text_path, text.as_bytes())
+    .map_err(|error| format!("No se pudo preparar el texto para audio: {error}"))?;
+
+  let mut command = Command::new("espeak-ng");
+  command
+    .arg("-w")
+    .arg(wav_path)
+    .arg("-s")
+    .arg(map_audio_rate_to_espeak(rate).to_string())
+    .arg("-a")
+    .arg(map_audio_volume_to_espeak(volume).to_string());
+  // `-v` cubre voz e idioma; la voz explicita tiene prioridad.
+  let voice = if voice.is_empty() { language } else { voice };
+  if !voice.is_empty() {
+    command.arg("-v").arg(voice);
+  }
+  command.arg("-f").arg(&text_path);
+
+  let command_result = command.output();
+  let _ = fs::remove_file(&text_path);
+
+  let output = command_result.map_err(|error| format!("No se pudo iniciar la exportacion de audio: {error}"))?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let detail = if !stderr.is_empty() {
+      stderr
+    } else {
+      format!("espeak-ng finalizo con codigo {:?}", output.status.code())
+    };
+    return Err(format!("Fallo al generar WAV: {detail}"));
+  }
+
+  Ok(())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn synthesize_segment_to_wav(
+  _text: &str,
+  _wav_path: &str,
+  _language: &str,
+  _voice: &str,
+  _rate: f32,
+  _volume: f32,
+) -> Result<(), String> {
+  Err("La exportacion de audiolibro WAV con voces del sistema no esta disponible en esta plataforma.".into())
+}
+
+/// Calcula la duracion en segundos de un WAV PCM leyendo los encabezados RIFF
+/// (tamano del bloque `data` dividido por el byte-rate del bloque `fmt `).
+fn wav_duration_seconds(path: &Path) -> Option<f64> {
+  let bytes = fs::read(path).ok()?;
+  if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+    return None;
+  }
+
+  let mut byte_rate: Option<u32> = None;
+  let mut data_len: Option<u32> = None;
+  let mut offset = 12;
+  while offset + 8 <= bytes.len() {
+    let chunk_id = &bytes[offset..offset + 4];
+    let chunk_size = u32::from_le_bytes([
+      bytes[offset + 4],
+      bytes[offset + 5],
+      bytes[offset + 6],
+      bytes[offset + 7],
+    ]) as usize;
+    let body = offset + 8;
+    if chunk_id == b"fmt " && body + 12 <= bytes.len() {
+      byte_rate = Some(u32::from_le_bytes([
+        bytes[body + 8],
+        bytes[body + 9],
+        bytes[body + 10],
+        bytes[body + 11],
+      ]));
+    } else if chunk_id == b"data" {
+      data_len = Some(chunk_size as u32);
+    }
+    // Los bloques RIFF se alinean a palabra (2 bytes).
+    offset = body + chunk_size + (chunk_size & 1);
+  }
+
+  match (byte_rate, data_len) {
+    (Some(rate), Some(len)) if rate > 0 => Some(len as f64 / f64::from(rate)),
+    _ => None,
+  }
+}
+
+/// Limpia un titulo de capitulo para usarlo como nombre de archivo.
+fn sanitize_file_stem(title: &str) -> String {
+  let cleaned: String = title
+    .trim()
+    .chars()
+    .map(|character| match character {
+      '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+      other if other.is_control() => '_',
+      other => other,
+    })
+    .collect();
+  let cleaned = cleaned.trim().to_string();
+  if cleaned.is_empty() {
+    "capitulo".to_string()
   } else {
-    Ok(exported_path)
+    cleaned
   }
 }
 
-#[cfg(not(target_os = "windows"))]
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AudiobookChapterInput {
+  title: String,
+  text: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportAudiobookChaptersInput {
+  chapters: Vec<AudiobookChapterInput>,
+  output_dir: String,
+  language: String,
+  voice_name: Option<String>,
+  rate: f32,
+  volume: f32,
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AudiobookChapterFile {
+  index: usize,
+  title: String,
+  path: String,
+  duration_seconds: f64,
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AudiobookManifest {
+  directory: String,
+  chapters: Vec<AudiobookChapterFile>,
+}
+
+/// Exporta un audiolibro en modo directorio: sintetiza cada capitulo a su propio
+/// WAV dentro de `output_dir` y devuelve un manifiesto con ruta y duracion por
+/// capitulo. Falla rapido indicando el indice del capitulo problematico.
 #[tauri::command]
-fn export_audiobook_wav(_input: ExportAudiobookInput) -> Result<String, String> {
-  Err("La exportacion de audiolibro WAV con voces del sistema esta disponible solo en Windows por ahora.".into())
+fn export_audiobook_chapters(input: ExportAudiobookChaptersInput) -> Result<AudiobookManifest, String> {
+  if input.chapters.is_empty() {
+    return Err("No hay capitulos para exportar.".into());
+  }
+
+  let output_dir = input.output_dir.trim();
+  if output_dir.is_empty() {
+    return Err("No se encontro la carpeta de salida para el audiolibro.".into());
+  }
+
+  fs::create_dir_all(output_dir)
+    .map_err(|error| format!("No se pudo preparar la carpeta de salida: {error}"))?;
+
+  let language = input.language.trim();
+  let voice = input.voice_name.unwrap_or_default();
+  let voice = voice.trim();
+
+  let mut chapters = Vec::with_capacity(input.chapters.len());
+  for (index, chapter) in input.chapters.iter().enumerate() {
+    let number = index + 1;
+    let text = chapter.text.trim();
+    if text.is_empty() {
+      return Err(format!("El capitulo {number} no tiene texto para exportar."));
+    }
+
+    let file_name = format!("{number:03} - {}.wav", sanitize_file_stem(&chapter.title));
+    let mut file_path = PathBuf::from(output_dir);
+    file_path.push(&file_name);
+    let file_path_str = file_path.to_string_lossy().to_string();
+
+    synthesize_segment_to_wav(text, &file_path_str, language, voice, input.rate, input.volume)
+      .map_err(|error| format!("Fallo el capitulo {number}: {error}"))?;
+
+    let duration_seconds = wav_duration_seconds(&file_path).unwrap_or(0.0);
+    chapters.push(AudiobookChapterFile {
+      index: number,
+      title: chapter.title.trim().to_string(),
+      path: file_path_str,
+      duration_seconds,
+    });
+  }
+
+  Ok(AudiobookManifest {
+    directory: output_dir.to_string(),
+    chapters,
+  })
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportAudiobookPiperInput {
+  text: String,
+  output_path: String,
+  model_path: String,
+  config_path: String,
+  piper_path: Option<String>,
+  speaker_id: Option<i64>,
+  rate: Option<f32>,
+  length_scale: Option<f32>,
+  noise_scale: Option<f32>,
+  noise_w: Option<f32>,
+  sentence_silence: Option<f32>,
+}
+
+/// Escapa una cadena para insertarla en un objeto JSON de una sola linea.
+fn escape_json_string(value: &str) -> String {
+  let mut escaped = String::with_capacity(value.len() + 2);
+  for character in value.chars() {
+    match character {
+      '"' => escaped.push_str("\\\""),
+      '\\' => escaped.push_str("\\\\"),
+      '\n' => escaped.push_str("\\n"),
+      '\r' => escaped.push_str("\\r"),
+      '\t' => escaped.push_str("\\t"),
+      control if (control as u32) < 0x20 => {
+        escaped.push_str(&format!("\\u{:04x}", control as u32));
+      }
+      other => escaped.push(other),
+    }
+  }
+  escaped
+}
+
+/// La UI expone 0.5–2.0 (mas alto = mas rapido); la escala de Piper es inversa
+/// (mas alto = mas lento), asi que se invierte.
+fn map_audio_rate_to_length_scale(rate: f32) -> f32 {
+  1.0 / rate.clamp(0.5, 2.0)
+}
+
+#[tauri::command]
+fn export_audiobook_piper(input: ExportAudiobookPiperInput) -> Result<String, String> {
+  use std::process::Stdio;
+
+  let normalized_text = input.text.trim();
+  if normalized_text.is_empty() {
+    return Err("No hay texto para exportar.".into());
+  }
+
+  let output_path = input.output_path.trim();
+  if output_path.is_empty() {
+    return Err("No se encontro la ruta de salida para el audiolibro.".into());
+  }
+
+  let model_path = input.model_path.trim();
+  if model_path.is_empty() {
+    return Err("No se encontro el modelo (.onnx) de Piper.".into());
+  }
+
+  let config_path = input.config_path.trim();
+  if config_path.is_empty() {
+    return Err("No se encontro la configuracion JSON del modelo de Piper.".into());
+  }
+
+  if let Some(parent) = PathBuf::from(output_path).parent() {
+    if !parent.as_os_str().is_empty() {
+      fs::create_dir_all(parent)
+        .map_err(|error| format!("No se pudo preparar la carpeta de salida: {error}"))?;
+    }
+  }
+
+  let length_scale = input
+    .length_scale
+    .unwrap_or_else(|| map_audio_rate_to_length_scale(input.rate.unwrap_or(1.0)));
+
+  let piper_path = input.piper_path.unwrap_or_default();
+  let piper_path = piper_path.trim();
+  let piper_path = if piper_path.is_empty() { "piper" } else { piper_path };
+
+  let mut command = Command::new(piper_path);
+  command
+    .arg("--model")
+    .arg(model_path)
+    .arg("--config")
+    .arg(config_path)
+    .arg("--output_file")
+    .arg(output_path)
+    .arg("--json-input")
+    .arg("--length_scale")
+    .arg(length_scale.to_string());
+
+  if let Some(noise_scale) = input.noise_scale {
+    command.arg("--noise_scale").arg(noise_scale.to_string());
+  }
+  if let Some(noise_w) = input.noise_w {
+    command.arg("--noise_w").arg(noise_w.to_string());
+  }
+  if let Some(sentence_silence) = input.sentence_silence {
+    command.arg("--sentence_silence").arg(sentence_silence.to_string());
+  }
+
+  // Para textos muy largos se vuelca la linea JSON a un archivo temporal y se
+  // redirige como stdin del proceso, igual que el resto de backends.
+  let payload = if let Some(speaker_id) = input.speaker_id {
+    format!(
+      "{{\"text\": \"{}\", \"speaker_id\": {speaker_id}}}\n",
+      escape_json_string(normalized_text)
+    )
+  } else {
+    format!("{{\"text\": \"{}\"}}\n", escape_json_string(normalized_text))
+  };
+
+  let text_path = build_temp_audio_text_path();
+  fs::write(&text_path, payload.as_bytes())
+    .map_err(|error| format!("No se pudo preparar el texto para audio: {error}"))?;
+
+  let stdin_file = match fs::File::open(&text_path) {
+    Ok(file) => file,
+    Err(error) => {
+      let _ = fs::remove_file(&text_path);
+      return Err(format!("No se pudo preparar el texto para audio: {error}"));
+    }
+  };
+
+  let command_result = command
+    .stdin(Stdio::from(stdin_file))
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .output();
+  let _ = fs::remove_file(&text_path);
+
+  let output = command_result.map_err(|error| format!("No se pudo iniciar Piper: {error}"))?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let detail = if !stderr.is_empty() {
+      stderr
+    } else {
+      format!("Piper finalizo con codigo {:?}", output.status.code())
+    };
+    return Err(format!("Fallo al generar WAV: {detail}"));
+  }
+
+  Ok(output_path.to_string())
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PreviewAudiobookInput {
+  text: String,
+  language: String,
+  voice_name: Option<String>,
+  rate: f32,
+  volume: f32,
+}
+
+/// Detiene la reproduccion de la vista previa en curso, si la hay.
+#[tauri::command]
+fn stop_preview() -> Result<(), String> {
+  if let Some(sink) = PREVIEW_SINK.lock().ok().and_then(|mut guard| guard.take()) {
+    sink.stop();
+  }
+  Ok(())
+}
+
+/// Sintetiza una seleccion corta a un WAV temporal y lo reproduce en proceso con
+/// rodio, respetando voz/velocidad/volumen. El archivo temporal se elimina al
+/// terminar la reproduccion.
+#[tauri::command]
+fn preview_audiobook(input: PreviewAudiobookInput) -> Result<(), String> {
+  use std::io::BufReader;
+  use std::sync::mpsc;
+
+  let normalized_text = input.text.trim();
+  if normalized_text.is_empty() {
+    return Err("No hay texto para la vista previa.".into());
+  }
+
+  // Cualquier vista previa anterior se detiene antes de empezar otra.
+  stop_preview()?;
+
+  let voice = input.voice_name.unwrap_or_default();
+  let voice = voice.trim();
+  let wav_path = build_temp_audio_wav_path();
+  let wav_path_str = wav_path.to_string_lossy().to_string();
+  synthesize_segment_to_wav(
+    normalized_text,
+    &wav_path_str,
+    input.language.trim(),
+    voice,
+    input.rate,
+    input.volume,
+  )?;
+
+  // La reproduccion vive en un hilo propio: el `OutputStream` no es Send y debe
+  // permanecer vivo mientras suena, asi que se retiene aqui hasta el final.
+  let (ready_tx, ready_rx) = mpsc::channel::<Result<(), String>>();
+  std::thread::spawn(move || {
+    let (_stream, stream_handle) = match rodio::OutputStream::try_default() {
+      Ok(pair) => pair,
+      Err(error) => {
+        let _ = ready_tx.send(Err(format!("No se pudo abrir el dispositivo de audio: {error}")));
+        let _ = fs::remove_file(&wav_path);
+        return;
+      }
+    };
+
+    let sink = match rodio::Sink::try_new(&stream_handle) {
+      Ok(sink) => sink,
+      Err(error) => {
+        let _ = ready_tx.send(Err(format!("No se pudo iniciar la reproduccion: {error}")));
+        let _ = fs::remove_file(&wav_path);
+        return;
+      }
+    };
+
+    let decoder = match fs::File::open(&wav_path).map_err(|error| error.to_string()).and_then(|file| {
+      rodio::Decoder::new(BufReader::new(file)).map_err(|error| error.to_string())
+    }) {
+      Ok(decoder) => decoder,
+      Err(error) => {
+        let _ = ready_tx.send(Err(format!("No se pudo leer la vista previa: {error}")));
+        let _ = fs::remove_file(&wav_path);
+        return;
+      }
+    };
+
+    sink.append(decoder);
+    let sink = std::sync::Arc::new(sink);
+    if let Ok(mut guard) = PREVIEW_SINK.lock() {
+      *guard = Some(sink.clone());
+    }
+    let _ = ready_tx.send(Ok(()));
+
+    sink.sleep_until_end();
+
+    if let Ok(mut guard) = PREVIEW_SINK.lock() {
+      if guard
+        .as_ref()
+        .map(|current| std::sync::Arc::ptr_eq(current, &sink))
+        .unwrap_or(false)
+      {
+        *guard = None;
+      }
+    }
+    let _ = fs::remove_file(&wav_path);
+  });
+
+  match ready_rx.recv() {
+    Ok(result) => result,
+    Err(_) => Err("No se pudo iniciar la reproduccion de la vista previa.".into()),
+  }
+}
+
+/// Voz de sintesis instalada, tal como la presenta el backend del sistema.
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AudiobookVoice {
+  name: String,
+  culture: String,
+  gender: String,
+  enabled: bool,
+}
+
+#[cfg(target_os = "windows")]
+const AUDIO_VOICES_SCRIPT: &str = r#"
+$ErrorActionPreference = 'Stop'
+Add-Type -AssemblyName System.Speech
+
+$synth = New-Object System.Speech.Synthesis.SpeechSynthesizer
+try {
+  foreach ($voice in $synth.GetInstalledVoices()) {
+    $info = $voice.VoiceInfo
+    $culture = if ($info.Culture) { $info.Culture.Name } else { '' }
+    Write-Output ($info.Name + "`t" + $culture + "`t" + $info.Gender + "`t" + $voice.Enabled)
+  }
+} finally {
+  $synth.Dispose()
+}
+"#;
+
+/// Enumera las voces instaladas para que la UI ofrezca un selector filtrado por
+/// idioma en lugar de pedir al usuario que escriba el nombre a ciegas.
+#[cfg(target_os = "windows")]
+#[tauri::command]
+fn list_audiobook_voices() -> Result<Vec<AudiobookVoice>, String> {
+  let output = Command::new("powershell")
+    .args(["-NoProfile", "-NonInteractive", "-Command", AUDIO_VOICES_SCRIPT])
+    .output()
+    .map_err(|error| format!("No se pudo enumerar las voces instaladas: {error}"))?;
+
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let detail = if stderr.is_empty() {
+      format!("PowerShell finalizo con codigo {:?}", output.status.code())
+    } else {
+      stderr
+    };
+    return Err(format!("Fallo al enumerar voces: {detail}"));
+  }
+
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  let mut voices = Vec::new();
+  for line in stdout.lines() {
+    if line.trim().is_empty() {
+      continue;
+    }
+    let mut parts = line.split('\t');
+    let name = parts.next().unwrap_or("").trim().to_string();
+    if name.is_empty() {
+      continue;
+    }
+    let culture = parts.next().unwrap_or("").trim().to_string();
+    let gender = parts.next().unwrap_or("").trim().to_string();
+    let enabled = parts
+      .next()
+      .map(|value| value.trim().eq_ignore_ascii_case("true"))
+      .unwrap_or(true);
+    voices.push(AudiobookVoice {
+      name,
+      culture,
+      gender,
+      enabled,
+    });
+  }
+
+  Ok(voices)
+}
+
+/// Enumera las voces de `say` (`say -v '?'`), cuyo listado no expone genero.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn list_audiobook_voices() -> Result<Vec<AudiobookVoice>, String> {
+  let output = Command::new("say")
+    .arg("-v")
+    .arg("?")
+    .output()
+    .map_err(|error| format!("No se pudo enumerar las voces instaladas: {error}"))?;
+
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let detail = if stderr.is_empty() {
+      format!("say finalizo con codigo {:?}", output.status.code())
+    } else {
+      stderr
+    };
+    return Err(format!("Fallo al enumerar voces: {detail}"));
+  }
+
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  let mut voices = Vec::new();
+  for line in stdout.lines() {
+    // Cada linea es `Nombre   locale  # texto de ejemplo`; el locale es el ultimo
+    // token antes del `#` y el nombre puede contener espacios.
+    let left = line.split('#').next().unwrap_or("").trim();
+    if left.is_empty() {
+      continue;
+    }
+    let mut tokens: Vec<&str> = left.split_whitespace().collect();
+    let culture = match tokens.pop() {
+      Some(culture) if !tokens.is_empty() => culture.replace('_', "-"),
+      _ => continue,
+    };
+    voices.push(AudiobookVoice {
+      name: tokens.join(" "),
+      culture,
+      gender: String::new(),
+      enabled: true,
+    });
+  }
+
+  Ok(voices)
+}
+
+/// Enumera las voces de espeak-ng (`espeak-ng --voices`), el mismo motor con el
+/// que se sintetiza en Linux, para que el selector y el sintetizador coincidan:
+/// el nombre devuelto es el identificador que acepta `espeak-ng -v`.
+#[cfg(target_os = "linux")]
+#[tauri::command]
+fn list_audiobook_voices() -> Result<Vec<AudiobookVoice>, String> {
+  let output = Command::new("espeak-ng")
+    .arg("--voices")
+    .output()
+    .map_err(|error| format!("No se pudo enumerar las voces instaladas: {error}"))?;
+
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let detail = if stderr.is_empty() {
+      format!("espeak-ng finalizo con codigo {:?}", output.status.code())
+    } else {
+      stderr
+    };
+    return Err(format!("Fallo al enumerar voces: {detail}"));
+  }
+
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  let mut voices = Vec::new();
+  for line in stdout.lines() {
+    // Columnas: `Pty Language Age/Gender VoiceName File ...`. Se omite la
+    // cabecera y se toma el codigo de idioma (columna 2), que es lo que `-v`
+    // acepta, y el genero de la columna `Age/Gender` (p. ej. `--/M`).
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if tokens.len() < 3 || tokens[0] == "Pty" {
+      continue;
+    }
+    let identifier = tokens[1];
+    let gender = tokens[2].rsplit('/').next().unwrap_or("");
+    voices.push(AudiobookVoice {
+      name: identifier.to_string(),
+      culture: identifier.to_string(),
+      gender: if gender == "M" || gender == "F" {
+        gender.to_string()
+      } else {
+        String::new()
+      },
+      enabled: true,
+    });
+  }
+
+  Ok(voices)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+#[tauri::command]
+fn list_audiobook_voices() -> Result<Vec<AudiobookVoice>, String> {
+  Err("La enumeracion de voces del sistema no esta disponible en esta plataforma.".into())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
   tauri::Builder::default()
-    .invoke_handler(tauri::generate_handler![export_audiobook_wav])
+    .invoke_handler(tauri::generate_handler![
+      export_audiobook_wav,
+      export_audiobook_piper,
+      export_audiobook_chapters,
+      preview_audiobook,
+      stop_preview,
+      list_audiobook_voices,
+      cancel_audiobook_export
+    ])
     .plugin(tauri_plugin_dialog::init())
     .plugin(tauri_plugin_fs::init())
     .setup(|app| {